@@ -0,0 +1,91 @@
+use crate::game::{Board, MoveOutcome, Player};
+
+/// Largest board `best_move` will search exhaustively. Plain minimax with no
+/// pruning or depth cutoff blows up well before a 4x4 board (16! leaf nodes
+/// in the worst case), so the computer opponent is refused past this size.
+pub const MAX_SQUARES: usize = 9;
+
+/// Play `square` on a clone of `board` and score the result from `ai_player`'s
+/// perspective, recursing via minimax if the game isn't over yet.
+fn score_after(board: &Board, square: usize, ai_player: Player, depth: i32) -> i32 {
+    let mut next = board.clone();
+    match next.play(square).expect("empty_squares only yields legal moves") {
+        MoveOutcome::Win(winner) if winner == ai_player => 10 - depth,
+        MoveOutcome::Win(_) => -10 + depth,
+        MoveOutcome::Draw => 0,
+        MoveOutcome::Pending => minimax(&next, ai_player, depth + 1),
+    }
+}
+
+/// Score `board` for the player to move: the maximizing player (`ai_player`)
+/// picks the highest-scoring reply, the minimizing player the lowest.
+fn minimax(board: &Board, ai_player: Player, depth: i32) -> i32 {
+    let maximizing = board.current_turn() == ai_player;
+    let scores = board
+        .empty_squares()
+        .into_iter()
+        .map(|square| score_after(board, square, ai_player, depth));
+    if maximizing {
+        scores.max()
+    } else {
+        scores.min()
+    }
+    .expect("minimax called on a board with no empty squares")
+}
+
+/// Exhaustively search every legal move and return the square that gives
+/// `ai_player` the best guaranteed outcome. Tiny enough on a 3x3 board to
+/// play perfectly.
+pub fn best_move(board: &Board, ai_player: Player) -> usize {
+    assert!(
+        board.size() * board.size() <= MAX_SQUARES,
+        "best_move called on a board larger than MAX_SQUARES; exhaustive search would not return"
+    );
+
+    let maximizing = board.current_turn() == ai_player;
+    board
+        .empty_squares()
+        .into_iter()
+        .map(|square| (square, score_after(board, square, ai_player, 1)))
+        .max_by_key(|&(_, score)| if maximizing { score } else { -score })
+        .map(|(square, _)| square)
+        .expect("best_move called on a board with no empty squares")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_an_immediate_win() {
+        let mut board = Board::new(3, 3);
+        // X: 0, 1; O: 3, 4. X to move, square 2 completes row 0.
+        for square in [0, 3, 1, 4] {
+            board.play(square).unwrap();
+        }
+        assert_eq!(best_move(&board, Player::X), 2);
+    }
+
+    #[test]
+    fn blocks_an_opponent_win() {
+        let mut board = Board::new(3, 3);
+        // X: 0, 1; O: 4. O to move and must block X's row-0 win at square 2.
+        for square in [0, 4, 1] {
+            board.play(square).unwrap();
+        }
+        assert_eq!(best_move(&board, Player::O), 2);
+    }
+
+    #[test]
+    fn perfect_play_from_an_empty_board_is_a_draw() {
+        let mut board = Board::new(3, 3);
+        loop {
+            let square = best_move(&board, board.current_turn());
+            match board.play(square).unwrap() {
+                MoveOutcome::Win(_) => panic!("perfect play on both sides should never lose"),
+                MoveOutcome::Draw => break,
+                MoveOutcome::Pending => continue,
+            }
+        }
+    }
+}
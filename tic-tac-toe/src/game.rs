@@ -0,0 +1,292 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Player {
+    X,
+    O,
+}
+
+impl Player {
+    pub fn other(self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+}
+
+impl Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Player::X => "X",
+                Player::O => "O",
+            }
+        )
+    }
+}
+
+/// The result of a successfully applied move.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MoveOutcome {
+    Pending,
+    Win(Player),
+    Draw,
+}
+
+/// Why a move could not be applied.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MoveError {
+    OutOfRange,
+    Occupied,
+    BadParse,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MoveError::OutOfRange => "out of range",
+                MoveError::Occupied => "square taken",
+                MoveError::BadParse => "not a number",
+            }
+        )
+    }
+}
+
+/// A 1-indexed `row,col` board coordinate, matching the 1-indexed square
+/// numbers printed on the board.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// Why a `"row,col"` string couldn't be parsed into a [`Pos`].
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct ParseError;
+
+impl FromStr for Pos {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s.split_once(',').ok_or(ParseError)?;
+        let x = x.trim().parse().map_err(|_| ParseError)?;
+        let y = y.trim().parse().map_err(|_| ParseError)?;
+        Ok(Pos { x, y })
+    }
+}
+
+#[derive(Clone)]
+pub struct Board {
+    grid: Vec<Vec<Option<Player>>>,
+    size: usize,
+    win_len: usize,
+    current_turn: Player,
+    moves: usize,
+}
+
+// the four directions we need to scan in to cover every row, column and
+// diagonal without writing a separate check for each
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+impl Board {
+    pub fn new(size: usize, win_len: usize) -> Board {
+        Board {
+            grid: vec![vec![None; size]; size],
+            size,
+            win_len,
+            current_turn: Player::X,
+            moves: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn current_turn(&self) -> Player {
+        self.current_turn
+    }
+
+    /// Override who moves first; used to let the loser of a session's last
+    /// round start the next one.
+    pub fn with_first_player(mut self, player: Player) -> Board {
+        self.current_turn = player;
+        self
+    }
+
+    /// Convert a 1-indexed `row,col` position into a square index for
+    /// `play`, bounds-checking it against this board's size.
+    pub fn square_of(&self, pos: Pos) -> Result<usize, MoveError> {
+        let row = pos.x.checked_sub(1).ok_or(MoveError::OutOfRange)?;
+        let col = pos.y.checked_sub(1).ok_or(MoveError::OutOfRange)?;
+        if row >= self.size || col >= self.size {
+            return Err(MoveError::OutOfRange);
+        }
+        Ok(row * self.size + col)
+    }
+
+    /// Every unoccupied square, as indices suitable for `play`.
+    pub fn empty_squares(&self) -> Vec<usize> {
+        (0..self.size * self.size)
+            .filter(|&square| self.grid[square / self.size][square % self.size].is_none())
+            .collect()
+    }
+
+    /// Place the current player's mark on `square` (0-indexed, row-major)
+    /// and advance the turn. Returns the outcome of the move, or an error
+    /// if the square couldn't be played.
+    pub fn play(&mut self, square: usize) -> Result<MoveOutcome, MoveError> {
+        if square >= self.size * self.size {
+            return Err(MoveError::OutOfRange);
+        }
+        let (row, col) = (square / self.size, square % self.size);
+        if self.grid[row][col].is_some() {
+            return Err(MoveError::Occupied);
+        }
+
+        self.grid[row][col] = Some(self.current_turn);
+        self.moves += 1;
+
+        if let Some(winner) = self.check_winner() {
+            return Ok(MoveOutcome::Win(winner));
+        }
+        if self.moves == self.size * self.size {
+            return Ok(MoveOutcome::Draw);
+        }
+
+        self.current_turn = self.current_turn.other();
+        Ok(MoveOutcome::Pending)
+    }
+
+    fn check_winner(&self) -> Option<Player> {
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let Some(player) = self.grid[row][col] else {
+                    continue;
+                };
+                for (dr, dc) in DIRECTIONS {
+                    let mut count = 1;
+                    let (mut r, mut c) = (row as isize, col as isize);
+                    while count < self.win_len {
+                        r += dr;
+                        c += dc;
+                        if r < 0 || c < 0 || r as usize >= self.size || c as usize >= self.size {
+                            break;
+                        }
+                        if self.grid[r as usize][c as usize] != Some(player) {
+                            break;
+                        }
+                        count += 1;
+                    }
+                    if count >= self.win_len {
+                        return Some(player);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_row_win() {
+        let mut board = Board::new(3, 3);
+        // X: 0 1 2, O: 3 4
+        for square in [0, 3, 1, 4, 2] {
+            match board.play(square) {
+                Ok(outcome) if square == 2 => assert_eq!(outcome, MoveOutcome::Win(Player::X)),
+                Ok(outcome) => assert_eq!(outcome, MoveOutcome::Pending),
+                Err(e) => panic!("unexpected error playing {square}: {e}"),
+            }
+        }
+    }
+
+    #[test]
+    fn detects_diagonal_win() {
+        let mut board = Board::new(3, 3);
+        // X: 0 4 8, O: 1 2
+        let outcome = [0, 1, 4, 2, 8]
+            .into_iter()
+            .map(|square| board.play(square).unwrap())
+            .last()
+            .unwrap();
+        assert_eq!(outcome, MoveOutcome::Win(Player::X));
+    }
+
+    #[test]
+    fn detects_k_in_a_row_on_larger_board() {
+        // 4x4 board needing 4 in a row: a 3-in-a-row is not yet a win.
+        let mut board = Board::new(4, 4);
+        for square in [0, 4, 1, 5, 2, 6] {
+            assert_eq!(board.play(square).unwrap(), MoveOutcome::Pending);
+        }
+        assert_eq!(board.play(3).unwrap(), MoveOutcome::Win(Player::X));
+    }
+
+    #[test]
+    fn full_board_with_no_line_is_a_draw() {
+        let mut board = Board::new(3, 3);
+        // X O X / X O O / O X X -- no line for either player.
+        let moves = [0, 1, 2, 4, 3, 5, 7, 6, 8];
+        let mut last = MoveOutcome::Pending;
+        for square in moves {
+            last = board.play(square).unwrap();
+        }
+        assert_eq!(last, MoveOutcome::Draw);
+    }
+
+    #[test]
+    fn play_rejects_occupied_and_out_of_range_squares() {
+        let mut board = Board::new(3, 3);
+        board.play(0).unwrap();
+        assert_eq!(board.play(0), Err(MoveError::Occupied));
+        assert_eq!(board.play(9), Err(MoveError::OutOfRange));
+    }
+
+    #[test]
+    fn pos_parses_trimmed_row_col_pairs() {
+        assert_eq!("1,2".parse(), Ok(Pos { x: 1, y: 2 }));
+        assert_eq!(" 3 , 4 ".parse(), Ok(Pos { x: 3, y: 4 }));
+        assert_eq!("1".parse::<Pos>(), Err(ParseError));
+        assert_eq!("a,b".parse::<Pos>(), Err(ParseError));
+    }
+
+    #[test]
+    fn square_of_is_1_indexed_and_bounds_checked() {
+        let board = Board::new(3, 3);
+        assert_eq!(board.square_of(Pos { x: 1, y: 1 }), Ok(0));
+        assert_eq!(board.square_of(Pos { x: 3, y: 3 }), Ok(8));
+        assert_eq!(board.square_of(Pos { x: 0, y: 1 }), Err(MoveError::OutOfRange));
+        assert_eq!(board.square_of(Pos { x: 4, y: 1 }), Err(MoveError::OutOfRange));
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rule = "-".repeat(self.size * 4 + 1);
+        writeln!(f, "{rule}")?;
+        for row in &self.grid {
+            for square in row {
+                write!(f, "|")?;
+                match square {
+                    Some(p) => write!(f, " {p} ")?,
+                    None => write!(f, "   ")?,
+                }
+            }
+            writeln!(f, "|")?;
+            writeln!(f, "{rule}")?;
+        }
+        Ok(())
+    }
+}
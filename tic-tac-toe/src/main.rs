@@ -1,127 +1,165 @@
-use std::{
-    fmt::Display,
-    io::{stdin, stdout, Write},
-};
-#[derive(PartialEq, Eq, Copy, Clone)]
-enum Player {
-    X,
-    O,
-}
-impl Display for Player {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Player::X => "X",
-                Player::O => "O",
-            }
-        )
-    }
-}
-struct Board {
-    grid: [[Option<Player>; 3]; 3],
-    current_turn: Player,
-    winner: Option<Player>,
-}
+mod ai;
+mod game;
 
-fn main() {
-    println!("tic tac toe!");
-    println!("Board squares are numbered as follows:");
-    println!(
-        "------------\n\
-        | 1 | 2 | 3 |\n\
-        -------------\n\
-        | 4 | 5 | 6 |\n\
-        -------------\n\
-        | 7 | 8 | 9 |\n\
-        -------------"
-    );
+use std::io::{stdin, stdout, Write};
+
+use game::{Board, MoveError, MoveOutcome, Player, Pos};
 
-    let mut board = Board {
-        grid: [[None, None, None], [None, None, None], [None, None, None]],
-        current_turn: Player::X,
-        winner: None,
-    };
+fn read_usize(prompt: &str) -> usize {
     loop {
-        print!("Player {}, enter a square>>", board.current_turn);
+        print!("{prompt}");
         stdout().flush().expect("Could not flush stdout");
 
-        let mut turn = String::new();
+        let mut line = String::new();
+        stdin().read_line(&mut line).expect("Failed to read line");
+        if let Ok(n) = line.trim().parse() {
+            return n;
+        }
+    }
+}
 
-        stdin().read_line(&mut turn).expect("Failed to read line");
-        let guess: Result<usize, _> = turn.trim().parse();
+fn read_line(prompt: &str) -> String {
+    print!("{prompt}");
+    stdout().flush().expect("Could not flush stdout");
 
-        if guess.is_err() {
-            continue;
-        }
-        let square = guess.unwrap() - 1;
-        if square > 8 || board.grid[square / 3][square % 3].is_some() {
-            continue;
+    let mut line = String::new();
+    stdin().read_line(&mut line).expect("Failed to read line");
+    line.trim().to_string()
+}
+
+/// Ask whether the computer should play, and if so which symbol it takes.
+/// The computer's exhaustive search is only practical on boards up to
+/// `ai::MAX_SQUARES`, so it isn't offered beyond that.
+fn read_computer_player(size: usize) -> Option<Player> {
+    if size * size > ai::MAX_SQUARES {
+        println!("Board too large for the computer to search exhaustively; staying human vs human.");
+        return None;
+    }
+    loop {
+        match read_line("human vs human or human vs computer>>").as_str() {
+            "human vs human" => return None,
+            "human vs computer" => {
+                return Some(loop {
+                    match read_line("should the computer play X or O>>").as_str() {
+                        "X" => break Player::X,
+                        "O" => break Player::O,
+                        _ => continue,
+                    }
+                })
+            }
+            _ => continue,
         }
+    }
+}
 
-        //print the board
-        board.grid[square / 3][square % 3] = Some(board.current_turn);
+/// Parse a move either as a 1-indexed square number or a `row,col` pair,
+/// then bounds-check it against `board`'s size.
+fn parse_square(input: &str, board: &Board) -> Result<usize, MoveError> {
+    let input = input.trim();
+    if let Some((_, _)) = input.split_once(',') {
+        let pos: Pos = input.parse().map_err(|_| MoveError::BadParse)?;
+        board.square_of(pos)
+    } else {
+        let square: usize = input.parse().map_err(|_| MoveError::BadParse)?;
+        square.checked_sub(1).ok_or(MoveError::OutOfRange)
+    }
+}
 
-        println!("-------------");
-        for row in board.grid {
-            for square in row {
-                print!("|");
-                match square {
-                    Some(p) => print!(" {p} "),
-                    None => print!("   "),
+/// Play one game to completion, printing the board after every move, and
+/// return how it ended.
+fn play_round(size: usize, win_len: usize, computer: Option<Player>, first: Player) -> MoveOutcome {
+    let mut board = Board::new(size, win_len).with_first_player(first);
+    loop {
+        let square = if computer == Some(board.current_turn()) {
+            let square = ai::best_move(&board, board.current_turn());
+            println!("Player {} (computer) plays {}", board.current_turn(), square + 1);
+            square
+        } else {
+            print!("Player {}, enter a square>>", board.current_turn());
+            stdout().flush().expect("Could not flush stdout");
+
+            let mut turn = String::new();
+            stdin().read_line(&mut turn).expect("Failed to read line");
+
+            match parse_square(&turn, &board) {
+                Ok(square) => square,
+                Err(e) => {
+                    println!("{e}");
+                    continue;
                 }
             }
-            println!("|");
-            println!("-------------");
-        }
+        };
 
-        //check if we have any winnders
-        //check rows -- easily done
-        for row in board.grid {
-            if row[0] == row[1] && row[1] == row[2] && row[0].is_some() {
-                board.winner = row[0];
+        match board.play(square) {
+            Ok(outcome) => {
+                print!("{board}");
+                match outcome {
+                    MoveOutcome::Win(winner) => {
+                        println!("{winner} wins");
+                        return MoveOutcome::Win(winner);
+                    }
+                    MoveOutcome::Draw => {
+                        println!("Draw -- no winner");
+                        return MoveOutcome::Draw;
+                    }
+                    MoveOutcome::Pending => (),
+                }
             }
-        }
-        //check columns -- need some indexing for this
-        for i in 0..3_usize {
-            if board.grid[0][i] == board.grid[1][i]
-                && board.grid[1][i] == board.grid[2][i]
-                && board.grid[0][i].is_some()
-            {
-                board.winner = board.grid[0][i];
+            Err(e) => {
+                println!("{e}");
+                continue;
             }
         }
-        //check diagonals
-        if board.grid[0][0] == board.grid[1][1]
-            && board.grid[1][1] == board.grid[2][2]
-            && board.grid[0][0].is_some()
-        {
-            board.winner = board.grid[0][0];
-        }
-        if board.grid[0][2] == board.grid[1][1]
-            && board.grid[1][1] == board.grid[2][0]
-            && board.grid[0][2].is_some()
-        {
-            board.winner = board.grid[0][2];
-        }
+    }
+}
 
-        match board.winner {
-            Some(Player::X) => {
-                print!("X wins");
-                break;
-            }
-            Some(Player::O) => {
-                print!("O wins");
-                break;
-            }
-            None => (),
-        }
+fn main() {
+    println!("tic tac toe!");
 
-        if board.current_turn == Player::X {
-            board.current_turn = Player::O;
-        } else {
-            board.current_turn = Player::X;
+    let size = read_usize("Enter board size>>");
+    let win_len = read_usize("How many in a row to win>>");
+    let computer = read_computer_player(size);
+
+    println!(
+        "Board squares are numbered 1 to {}, left to right, top to bottom.\n\
+        Enter a square number, or a \"row,col\" pair (e.g. 1,2).",
+        size * size
+    );
+
+    let mut x_wins = 0u32;
+    let mut o_wins = 0u32;
+    let mut next_first = Player::X;
+    let mut last_winner = None;
+
+    loop {
+        match read_line("start, scoreboard, or quit>>").as_str() {
+            "start" => {
+                let first = match read_line("who goes first, X, O, or blank for default>>").as_str() {
+                    "X" => Player::X,
+                    "O" => Player::O,
+                    _ => next_first,
+                };
+
+                match play_round(size, win_len, computer, first) {
+                    MoveOutcome::Win(winner) => {
+                        match winner {
+                            Player::X => x_wins += 1,
+                            Player::O => o_wins += 1,
+                        }
+                        next_first = winner.other();
+                        last_winner = Some(winner);
+                    }
+                    MoveOutcome::Draw => {
+                        if let Some(winner) = last_winner {
+                            next_first = winner;
+                        }
+                    }
+                    MoveOutcome::Pending => unreachable!("play_round always ends in a win or draw"),
+                }
+            }
+            "scoreboard" => println!("X: {x_wins}  O: {o_wins}"),
+            "quit" => break,
+            _ => continue,
         }
     }
 }